@@ -17,21 +17,23 @@
 //!   For value >=128 use typenum's consts.
 //! 
 //! ### Dimensions
-//! 
-//! For one-dimensional decoder returns simply items.
-//! 
-//! For more dimensions, output is a `Vec` of values containing a single item.
-//! 
-//! E.g. a 3-dimensional decoder where items are of size 4x4 will return `Vec`s
-//! of length 16.
-//! 
+//!
+//! For a one-dimensional decoder the iterator returns simply items.
+//!
+//! For more dimensions, output is a `Vec` of values containing a single item,
+//! whose length is the product of every dimension but the first. E.g. a
+//! 3-dimensional decoder where items are of size 4x4 returns `Vec`s of length 16.
+//!
 //! First dimension of decoder corresponds to amount of items left.
-//! 
+//!
 //! ## Caveats
-//! 
-//! Currently decoder only implements Iterator for 1 and 3 dimensions.
-//! It's simply because I didn't implement other.
-//! 
+//!
+//! Decoder implements Iterator for one-dimensional files (yielding scalars)
+//! and for two- up to 127-dimensional files (yielding `Vec`s) — every named
+//! dimension nalgebra's `U*` types provide. Files with 128 or more
+//! dimensions would need `D` to be `nalgebra::Const<N>` with `N >= 128`, for
+//! which no `Iterator` impl exists.
+//!
 //! Crate also assumes that items are stored in big endian way, just like sizes.
 //! 
 //! If you found a bug or the crate is missing some functionality,
@@ -42,7 +44,7 @@
 //! let file = std::fs::File::open("data.idx")?;
 //! let decode = idx_decoder::IDXDecoder::<_, idx_decoder::types::U8, nalgebra::U1>::new(file)?;
 //! for item in decode {
-//!     println!("Item: {}", item);
+//!     println!("Item: {}", item?);
 //! }
 //! ```
 //! 
@@ -52,19 +54,87 @@
 //! 
 //! [`IDXDecoder`]: struct.IDXDecoder.html
 
-use std::{convert::TryInto, io::{self, Read}, marker::PhantomData};
-use nalgebra::{self as na, VectorN, DimName, allocator::Allocator, DefaultAllocator};
+#![cfg_attr(not(feature = "std"), no_std)]
+// `failure_derive` expands to trait impls in a const block, which newer rustc
+// flags as non-local; the generated code is correct, so silence it here.
+#![cfg_attr(feature = "std", allow(non_local_definitions))]
+
+#[cfg(not(feature = "std"))]
+#[macro_use]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use core::{convert::TryInto, marker::PhantomData};
+use nalgebra::{self as na, OVector, DimName, allocator::Allocator, DefaultAllocator};
 // use typenum::{self as tn, type_operators::IsLess};
+
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
+#[cfg(feature = "std")]
 use failure::Fail;
 
+#[cfg(not(feature = "std"))]
+use io_nostd::Read;
+
+/// Minimal byte source used when the `std` feature is turned off.
+///
+/// It mirrors the part of [`std::io::Read`] the decoder needs, so a caller on a
+/// microcontroller can plug in flash or a socket without pulling in `std`.
+#[cfg(not(feature = "std"))]
+pub mod io_nostd {
+    /// Error returned by the no_std [`Read`] trait.
+    #[derive(Debug)]
+    pub enum Error {
+        /// Reader ran out of bytes before `buf` was filled.
+        UnexpectedEof,
+        /// Any other, source-defined failure.
+        Other,
+    }
+
+    /// `std::io::Read`-like trait the decoder is generic over in `no_std` builds.
+    pub trait Read {
+        fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Error>;
+    }
+}
+
+/// Inner error carried by [`IDXError::IOError`].
+#[cfg(feature = "std")]
+type IoError = io::Error;
+#[cfg(not(feature = "std"))]
+type IoError = io_nostd::Error;
+
 /// Types used by [`IDXDecoder`](struct.IDXDecoder.html) to specify iterator's output type
 pub mod types {
-    use std::{io::Read, mem::size_of};
+    use core::mem::size_of;
+    #[cfg(feature = "std")]
+    use std::io::{self, Read, Write};
+    #[cfg(not(feature = "std"))]
+    use super::io_nostd::{self, Read};
 
     #[doc(hidden)]
     mod private { pub trait Sealed {} }
     use private::Sealed;
 
+    // Reads exactly `buf.len()` bytes, mapping a short read to
+    // `IDXError::UnexpectedEof` so truncation can be told apart from a clean
+    // end-of-stream (which the iterator signals by returning `None`).
+    #[cfg(feature = "std")]
+    fn read_full<R: Read>(r: &mut R, buf: &mut [u8]) -> Result<(), super::IDXError> {
+        r.read_exact(buf).map_err(|e| match e.kind() {
+            io::ErrorKind::UnexpectedEof => super::IDXError::UnexpectedEof,
+            _ => super::IDXError::IOError(e),
+        })
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn read_full<R: Read>(r: &mut R, buf: &mut [u8]) -> Result<(), super::IDXError> {
+        r.read_exact(buf).map_err(|e| match e {
+            io_nostd::Error::UnexpectedEof => super::IDXError::UnexpectedEof,
+            other => super::IDXError::IOError(other),
+        })
+    }
+
     /// Trait implemented by output types used by IDXDecoder's iterator
     /// 
     /// It can't be implemented outside this crate.
@@ -76,7 +146,14 @@ pub mod types {
     // implemented by types that can be read from reader using big endiann
     #[doc(hidden)]
     pub trait BEReadable<R>: Sized {
-        fn read_self(r: &mut R) -> Option<Self>;
+        fn read_self(r: &mut R) -> Result<Self, super::IDXError>;
+    }
+
+    // implemented by types that can be written to writer using big endiann
+    #[cfg(feature = "std")]
+    #[doc(hidden)]
+    pub trait BEWritable<W>: Sized {
+        fn write_self(&self, w: &mut W) -> io::Result<()>;
     }
 
     macro_rules! new_type_int {
@@ -90,10 +167,17 @@ pub mod types {
                 }
 
                 impl<R: Read> BEReadable<R> for $tv {
-                    fn read_self(r: &mut R) -> Option<Self> {
+                    fn read_self(r: &mut R) -> Result<Self, super::IDXError> {
                         let mut buf = [0u8; size_of::<Self>()];
-                        r.read_exact(&mut buf).ok()?;
-                        Some(Self::from_be_bytes(buf))
+                        read_full(r, &mut buf)?;
+                        Ok(Self::from_be_bytes(buf))
+                    }
+                }
+
+                #[cfg(feature = "std")]
+                impl<W: Write> BEWritable<W> for $tv {
+                    fn write_self(&self, w: &mut W) -> io::Result<()> {
+                        w.write_all(&self.to_be_bytes())
                     }
                 }
             )*
@@ -111,10 +195,17 @@ pub mod types {
                 }
 
                 impl<R: Read> BEReadable<R> for $tv {
-                    fn read_self(r: &mut R) -> Option<Self> {
+                    fn read_self(r: &mut R) -> Result<Self, super::IDXError> {
                         let mut buf = [0u8; size_of::<Self>()];
-                        r.read_exact(&mut buf).ok()?;
-                        Some(Self::from_bits(<$uint>::from_be_bytes(buf)))
+                        read_full(r, &mut buf)?;
+                        Ok(Self::from_bits(<$uint>::from_be_bytes(buf)))
+                    }
+                }
+
+                #[cfg(feature = "std")]
+                impl<W: Write> BEWritable<W> for $tv {
+                    fn write_self(&self, w: &mut W) -> io::Result<()> {
+                        w.write_all(&self.to_bits().to_be_bytes())
                     }
                 }
             )*
@@ -142,28 +233,79 @@ where
 {
     reader: R,
     output_type: PhantomData<T>,
-    dimensions: VectorN<u32, D>,
+    dimensions: OVector<u32, D>,
+    item_len: usize,
 }
 
 /// Error type return by `IDXDecoder::new`
-#[derive(Debug, Fail)]
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(Fail))]
 pub enum IDXError {
-    #[fail(display = "Wrong magic, first two bytes should be zero")]
+    #[cfg_attr(feature = "std", fail(display = "Wrong magic, first two bytes should be zero"))]
     WrongMagic,
-    #[fail(display = "Wrong type, expected {}, got {}", _0, _1)]
+    #[cfg_attr(feature = "std", fail(display = "Wrong type, expected {}, got {}", _0, _1))]
     WrongType(u8, u8),
-    #[fail(display = "Wrong number of dimensions, expected {}, got {}", _0, _1)]
+    #[cfg_attr(feature = "std", fail(display = "Wrong number of dimensions, expected {}, got {}", _0, _1))]
     WrongDimensions(u8, u8),
-    #[fail(display = "{}", _0)]
-    IOError(#[cause] io::Error),
+    #[cfg_attr(feature = "std", fail(display = "Stream ended in the middle of an item"))]
+    UnexpectedEof,
+    #[cfg_attr(feature = "std", fail(display = "Item has {} values, but the target type expects {}", _1, _0))]
+    ItemLength(usize, usize),
+    #[cfg_attr(feature = "std", fail(display = "Wrote {} items, but the header declares {}", _1, _0))]
+    ItemCount(u32, u32),
+    #[cfg_attr(feature = "std", fail(display = "{}", _0))]
+    IOError(#[cfg_attr(feature = "std", cause)] IoError),
 }
 
-impl From<io::Error> for IDXError {
-    fn from(error: io::Error) -> Self {
+impl From<IoError> for IDXError {
+    fn from(error: IoError) -> Self {
         IDXError::IOError(error)
     }
 }
 
+// `failure` doesn't build without `std`, so provide the `Display` impl the
+// derive would have generated by hand when the feature is off.
+#[cfg(not(feature = "std"))]
+impl core::fmt::Display for IDXError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            IDXError::WrongMagic => write!(f, "Wrong magic, first two bytes should be zero"),
+            IDXError::WrongType(e, g) => write!(f, "Wrong type, expected {}, got {}", e, g),
+            IDXError::WrongDimensions(e, g) => write!(f, "Wrong number of dimensions, expected {}, got {}", e, g),
+            IDXError::UnexpectedEof => write!(f, "Stream ended in the middle of an item"),
+            IDXError::ItemLength(e, g) => write!(f, "Item has {} values, but the target type expects {}", g, e),
+            IDXError::ItemCount(e, g) => write!(f, "Wrote {} items, but the header declares {}", g, e),
+            IDXError::IOError(_) => write!(f, "I/O error while decoding"),
+        }
+    }
+}
+
+// Produced when an item's length overflows `usize`.
+#[cfg(feature = "std")]
+fn overflow_error() -> IDXError {
+    IDXError::IOError(io::Error::from(io::ErrorKind::InvalidData))
+}
+#[cfg(not(feature = "std"))]
+fn overflow_error() -> IDXError {
+    IDXError::IOError(io_nostd::Error::Other)
+}
+
+// Every item is a flat block whose length is the product of all dimensions
+// but the first (the first being the amount of items). Guards against
+// `u32` -> `usize` overflow. Shared by both `IDXDecoder` and `IDXEncoder` so
+// the two can't drift apart on how that length is computed.
+fn item_len_of<D: DimName>(dimensions: &OVector<u32, D>) -> Result<usize, IDXError>
+where
+    DefaultAllocator: Allocator<u32, D>,
+{
+    let mut item_len: usize = 1;
+    for d in dimensions.iter().skip(1) {
+        let d: usize = (*d).try_into().map_err(|_| overflow_error())?;
+        item_len = item_len.checked_mul(d).ok_or_else(overflow_error)?;
+    }
+    Ok(item_len)
+}
+
 impl<R: Read, T: Type, D: DimName> IDXDecoder<R, T, D>
 where
     // D: IsLess<tn::consts::U256>,
@@ -176,71 +318,287 @@ where
         reader.read_exact(&mut buf)?;
         if buf[0] != 0 || buf[1] != 0 { Err(IDXError::WrongMagic)? }
         if buf[2] != T::VALUE { Err(IDXError::WrongType(T::VALUE, buf[2]))? }
-        let dims: u8 = D::dim().try_into().ok()?;
+        let dims: u8 = D::dim().try_into().map_err(|_| overflow_error())?;
         if buf[3] != dims { Err(IDXError::WrongDimensions(dims, buf[3]))? }
 
         // Read dimensions
         // To simplify code we treat amount of items as first dimension
-        let mut dimensions: VectorN<u32, D> = na::zero();
+        let mut dimensions: OVector<u32, D> = na::zero();
         for d in dimensions.iter_mut() {
             let mut buf = [0u8; 4];
             reader.read_exact(&mut buf)?;
             *d = u32::from_be_bytes(buf);
         }
-        Ok(IDXDecoder { reader, output_type: PhantomData, dimensions })
+
+        let item_len = item_len_of(&dimensions)?;
+
+        Ok(IDXDecoder { reader, output_type: PhantomData, dimensions, item_len })
     }
 
     /// Size of return values.
     /// 
     /// First dimension of decoder corresponds to amount of items left.
-    pub fn dimensions(&self) -> VectorN<u32, D> {
+    pub fn dimensions(&self) -> OVector<u32, D> {
         self.dimensions.clone()
     }
 }
 
+// One-dimensional files stay scalar, just like the original hand-written impl.
 impl<R: Read, T: Type> Iterator for IDXDecoder<R, T, na::U1>
 where
     DefaultAllocator: Allocator<u32, na::U1>,
     T::TypeValue: BEReadable<R>,
 {
-    type Item = T::TypeValue;
+    type Item = Result<T::TypeValue, IDXError>;
     fn next(&mut self) -> Option<Self::Item> {
         if self.dimensions[0] > 0 {
             self.dimensions[0] -= 1;
-            T::TypeValue::read_self(&mut self.reader)
+            Some(T::TypeValue::read_self(&mut self.reader))
         } else {
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.dimensions[0].try_into().ok())
+    }
+}
+
+// Everything with two or more dimensions comes back as a flat `Vec` whose
+// length is the precomputed `item_len`. A single blanket impl can't coexist
+// with the 1-D scalar impl above (it would overlap on `na::U1`), so — as the
+// crate already did for `U1`/`U3` — the impl is generated per dimension. The
+// list below covers every named dimension nalgebra provides (`U2`..`U127`);
+// going further would mean accepting `D: DimName` generically and somehow
+// excluding just `U1`, which coherence doesn't let us express.
+macro_rules! impl_vec_iter {
+    ( $( $dim:ident ),* $(,)? ) => {
+        $(
+            impl<R: Read, T: Type> Iterator for IDXDecoder<R, T, na::$dim>
+            where
+                DefaultAllocator: Allocator<u32, na::$dim>,
+                T::TypeValue: Default + Clone + BEReadable<R>,
+            {
+                type Item = Result<Vec<T::TypeValue>, IDXError>;
+                fn next(&mut self) -> Option<Self::Item> {
+                    if self.dimensions[0] > 0 {
+                        self.dimensions[0] -= 1;
+                        let mut items = vec![Default::default(); self.item_len];
+                        for item in items.iter_mut() {
+                            match T::TypeValue::read_self(&mut self.reader) {
+                                Ok(value) => *item = value,
+                                Err(e) => return Some(Err(e)),
+                            }
+                        }
+                        Some(Ok(items))
+                    } else {
+                        None
+                    }
+                }
+
+                fn size_hint(&self) -> (usize, Option<usize>) {
+                    (0, self.dimensions[0].try_into().ok())
+                }
+            }
+        )*
+    };
+}
+
+impl_vec_iter!(
+    U2, U3, U4, U5, U6, U7, U8, U9, U10, U11, U12, U13, U14, U15, U16, U17, U18, U19, U20, U21,
+    U22, U23, U24, U25, U26, U27, U28, U29, U30, U31, U32, U33, U34, U35, U36, U37, U38, U39, U40,
+    U41, U42, U43, U44, U45, U46, U47, U48, U49, U50, U51, U52, U53, U54, U55, U56, U57, U58, U59,
+    U60, U61, U62, U63, U64, U65, U66, U67, U68, U69, U70, U71, U72, U73, U74, U75, U76, U77, U78,
+    U79, U80, U81, U82, U83, U84, U85, U86, U87, U88, U89, U90, U91, U92, U93, U94, U95, U96, U97,
+    U98, U99, U100, U101, U102, U103, U104, U105, U106, U107, U108, U109, U110, U111, U112, U113,
+    U114, U115, U116, U117, U118, U119, U120, U121, U122, U123, U124, U125, U126, U127,
+);
+
+/// Turns one raw IDX item into a user type.
+///
+/// Unlike [`Type`](types/trait.Type.html) this trait is not sealed, so
+/// downstream crates can decode straight into their own image or tensor types.
+/// `dims` is the shape of a single item (every file dimension but the first)
+/// and `data` its flat, row-major values.
+pub trait FromIdxItem<T: Type>: Sized {
+    fn from_raw(dims: &[u32], data: &[T::TypeValue]) -> Result<Self, IDXError>;
+}
+
+/// A 28x28 MNIST image becomes a `Matrix` directly, etc.
+impl<T: Type, R: DimName, C: DimName> FromIdxItem<T> for na::OMatrix<T::TypeValue, R, C>
+where
+    T::TypeValue: na::Scalar,
+    DefaultAllocator: Allocator<T::TypeValue, R, C>,
+{
+    fn from_raw(_dims: &[u32], data: &[T::TypeValue]) -> Result<Self, IDXError> {
+        let expected = R::dim() * C::dim();
+        if data.len() != expected {
+            return Err(IDXError::ItemLength(expected, data.len()));
+        }
+        Ok(Self::from_row_slice(data))
+    }
 }
 
-impl<R: Read, T: Type> Iterator for IDXDecoder<R, T, na::U3>
+/// Items whose length is known at compile time can come back as plain arrays.
+impl<T: Type, const N: usize> FromIdxItem<T> for [T::TypeValue; N]
 where
-    DefaultAllocator: Allocator<u32, na::U3>,
-    T::TypeValue: Default + Clone + BEReadable<R>,
+    T::TypeValue: Default + Copy,
 {
-    type Item = Vec<T::TypeValue>;
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.dimensions[0] > 0 {
-            self.dimensions[0] -= 1;
-            let as_usize = |n: u32| -> Option<usize> { n.try_into().ok() };
-            let len = as_usize(self.dimensions[1])?.checked_mul(as_usize(self.dimensions[2])?)?;
-            let mut items = vec![Default::default(); len];
-            for item in items.iter_mut() {
-                *item = T::TypeValue::read_self(&mut self.reader)?
-            }
-            Some(items)
-        } else {
-            None
+    fn from_raw(_dims: &[u32], data: &[T::TypeValue]) -> Result<Self, IDXError> {
+        if data.len() != N {
+            return Err(IDXError::ItemLength(N, data.len()));
         }
+        let mut out = [Default::default(); N];
+        out.copy_from_slice(&data[..N]);
+        Ok(out)
     }
+}
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (0, self.dimensions[0].try_into().ok())
+impl<R: Read, T: Type, D: DimName> IDXDecoder<R, T, D>
+where
+    DefaultAllocator: Allocator<u32, D>,
+    Self: Iterator<Item = Result<Vec<T::TypeValue>, IDXError>>,
+{
+    /// Decodes items straight into any [`FromIdxItem`] type, keeping the same
+    /// truncation reporting as the plain iterator.
+    pub fn map_items<O: FromIdxItem<T>>(self) -> impl Iterator<Item = Result<O, IDXError>> {
+        let item_dims: Vec<u32> = self.dimensions.iter().skip(1).copied().collect();
+        self.map(move |item| item.and_then(|data| O::from_raw(&item_dims, &data)))
+    }
+}
+
+/// The encoder. Counterpart of [`IDXDecoder`](struct.IDXDecoder.html) that
+/// writes IDX data instead of reading it.
+///
+/// Like the decoder it takes three type parameters: the writer `W`, the item
+/// type `T` and the type-level number of dimensions `D`. Creating an encoder
+/// writes the 4-byte magic followed by every dimension as a big endiann `u32`;
+/// afterwards items are pushed either one numeric value at a time with
+/// [`write_item`](struct.IDXEncoder.html#method.write_item) or in bulk through
+/// `write_items`. Both exist for 1-D encoders; 3-D encoders have `write_items`
+/// only, and encoders of any other dimension count have neither.
+///
+/// The header declares the item count (`dimensions[0]`) up front, so a file
+/// where fewer or more items are written than declared would silently lie
+/// about its own length. Call [`finish`](struct.IDXEncoder.html#method.finish)
+/// once every item has been written through `write_items` to check the two
+/// match.
+#[cfg(feature = "std")]
+pub struct IDXEncoder<W, T: Type, D: DimName>
+where
+    DefaultAllocator: Allocator<u32, D>
+{
+    writer: W,
+    output_type: PhantomData<T>,
+    dimensions: OVector<u32, D>,
+    items_written: u32,
+}
+
+#[cfg(feature = "std")]
+impl<W: Write, T: Type, D: DimName> IDXEncoder<W, T, D>
+where
+    DefaultAllocator: Allocator<u32, D>
+{
+    /// Writes the header and returns an encoder ready to accept items.
+    ///
+    /// First dimension corresponds to amount of items, just like in the decoder.
+    pub fn new(mut writer: W, dimensions: OVector<u32, D>) -> Result<Self, IDXError> {
+        let dims: u8 = D::dim().try_into().map_err(|_| overflow_error())?;
+        writer.write_all(&[0, 0, T::VALUE, dims])?;
+        for d in dimensions.iter() {
+            writer.write_all(&d.to_be_bytes())?;
+        }
+        Ok(IDXEncoder { writer, output_type: PhantomData, dimensions, items_written: 0 })
+    }
+
+    /// Dimensions the encoder was created with.
+    pub fn dimensions(&self) -> OVector<u32, D> {
+        self.dimensions.clone()
+    }
+
+    /// Checks that the number of items written matches the count declared in
+    /// the header, instead of letting a short (or over-long) write pass
+    /// silently.
+    pub fn finish(self) -> Result<(), IDXError> {
+        if self.items_written != self.dimensions[0] {
+            return Err(IDXError::ItemCount(self.dimensions[0], self.items_written));
+        }
+        Ok(())
+    }
+
+    /// Product of every dimension but the first, i.e. the number of values
+    /// a single item is made of.
+    fn item_len(&self) -> Result<usize, IDXError> {
+        item_len_of(&self.dimensions)
     }
 }
 
-#[cfg(test)]
+#[cfg(feature = "std")]
+impl<W: Write, T: Type> IDXEncoder<W, T, na::U1>
+where
+    DefaultAllocator: Allocator<u32, na::U1>,
+    T::TypeValue: BEWritable<W>,
+{
+    /// Writes a single item of a 1-D dataset, for streaming output one value
+    /// at a time. Errors instead of writing past the item count declared in
+    /// the header; call [`finish`](struct.IDXEncoder.html#method.finish)
+    /// afterwards to check the count wasn't left short.
+    pub fn write_item(&mut self, item: &T::TypeValue) -> Result<(), IDXError> {
+        if self.items_written >= self.dimensions[0] {
+            return Err(IDXError::ItemCount(self.dimensions[0], self.items_written));
+        }
+        item.write_self(&mut self.writer)?;
+        self.items_written += 1;
+        Ok(())
+    }
+
+    /// Writes every value of a 1-D dataset, surfacing the first write error
+    /// instead of silently truncating the output. Errors instead of writing
+    /// past the item count declared in the header; call
+    /// [`finish`](struct.IDXEncoder.html#method.finish) afterwards to check
+    /// the count wasn't left short.
+    pub fn write_items<I: IntoIterator<Item = T::TypeValue>>(&mut self, iter: I) -> Result<(), IDXError> {
+        for item in iter {
+            self.write_item(&item)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: Write, T: Type> IDXEncoder<W, T, na::U3>
+where
+    DefaultAllocator: Allocator<u32, na::U3>,
+    T::TypeValue: BEWritable<W>,
+{
+    /// Writes every value of each item of a 3-D dataset, surfacing the first
+    /// write error instead of silently truncating the output. Errors instead
+    /// of writing past the item count declared in the header, or if an item's
+    /// length doesn't match the one implied by the header's dimensions; call
+    /// [`finish`](struct.IDXEncoder.html#method.finish) afterwards to check
+    /// the count wasn't left short.
+    pub fn write_items<I: IntoIterator<Item = Vec<T::TypeValue>>>(&mut self, iter: I) -> Result<(), IDXError> {
+        let item_len = self.item_len()?;
+        for item in iter {
+            if self.items_written >= self.dimensions[0] {
+                return Err(IDXError::ItemCount(self.dimensions[0], self.items_written));
+            }
+            if item.len() != item_len {
+                return Err(IDXError::ItemLength(item_len, item.len()));
+            }
+            for value in &item {
+                value.write_self(&mut self.writer)?;
+            }
+            self.items_written += 1;
+        }
+        Ok(())
+    }
+}
+
+// Every test below reaches for `std::io::Cursor` and/or `IDXEncoder`, both of
+// which are `std`-only, so the whole module needs the feature too —
+// otherwise `cargo test --no-default-features` fails to even compile.
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use crate::*;
 
@@ -256,10 +614,42 @@ mod tests {
         let reader = std::io::Cursor::new(DATA);
         let mut decoder = IDXDecoder::<_, U8, nalgebra::U1>::new(reader)
             .expect("Decoder creation error");
-        assert_eq!(decoder.next(), Some(1));
-        assert_eq!(decoder.next(), Some(2));
-        assert_eq!(decoder.next(), Some(3));
-        assert_eq!(decoder.next(), None);
+        assert_eq!(decoder.next().unwrap().unwrap(), 1);
+        assert_eq!(decoder.next().unwrap().unwrap(), 2);
+        assert_eq!(decoder.next().unwrap().unwrap(), 3);
+        assert!(decoder.next().is_none());
+    }
+
+    #[test]
+    fn example_2d() {
+        const DATA: &[u8] = &[
+            // magic, type u8, 2 dims: 2 rows of 3
+            0, 0, 8, 2,
+            0, 0, 0, 2,
+            0, 0, 0, 3,
+            // items
+            1, 2, 3,
+            4, 5, 6];
+        let reader = std::io::Cursor::new(DATA);
+        let mut decoder = IDXDecoder::<_, U8, nalgebra::U2>::new(reader)
+            .expect("Decoder creation error");
+        assert_eq!(decoder.next().unwrap().unwrap(), vec![1, 2, 3]);
+        assert_eq!(decoder.next().unwrap().unwrap(), vec![4, 5, 6]);
+        assert!(decoder.next().is_none());
+    }
+
+    #[test]
+    fn truncated_1d_is_error() {
+        const DATA: &[u8] = &[
+            // magic, type i16, 1 dim, len 2, but only one value and a half
+            0, 0, 0x0b, 1,
+            0, 0, 0, 2,
+            0, 5, 0];
+        let reader = std::io::Cursor::new(DATA);
+        let mut decoder = IDXDecoder::<_, I16, nalgebra::U1>::new(reader)
+            .expect("Decoder creation error");
+        assert_eq!(decoder.next().unwrap().unwrap(), 5);
+        assert!(matches!(decoder.next(), Some(Err(IDXError::UnexpectedEof))));
     }
 
     #[test]
@@ -278,9 +668,132 @@ mod tests {
         let reader = std::io::Cursor::new(DATA);
         let mut decoder = IDXDecoder::<_, U8, nalgebra::U3>::new(reader)
             .expect("Decoder creation error");
-        assert_eq!(decoder.next(), Some(vec![1, 2, 3, 4]));
-        assert_eq!(decoder.next(), Some(vec![5, 6, 7, 8]));
-        assert_eq!(decoder.next(), Some(vec![9, 10, 11, 12]));
-        assert_eq!(decoder.next(), None);
+        assert_eq!(decoder.next().unwrap().unwrap(), vec![1, 2, 3, 4]);
+        assert_eq!(decoder.next().unwrap().unwrap(), vec![5, 6, 7, 8]);
+        assert_eq!(decoder.next().unwrap().unwrap(), vec![9, 10, 11, 12]);
+        assert!(decoder.next().is_none());
+    }
+
+    #[test]
+    fn encode_1d() {
+        let mut writer = std::io::Cursor::new(Vec::new());
+        let mut encoder = IDXEncoder::<_, U8, nalgebra::U1>::new(&mut writer, nalgebra::Vector1::new(3))
+            .expect("Encoder creation error");
+        encoder.write_items(vec![1u8, 2, 3]).expect("write error");
+        encoder.finish().expect("item count mismatch");
+        assert_eq!(writer.into_inner(), &[0, 0, 8, 1, 0, 0, 0, 3, 1, 2, 3]);
+    }
+
+    #[test]
+    fn encode_1d_short_write_is_item_count_error() {
+        let mut writer = std::io::Cursor::new(Vec::new());
+        let mut encoder = IDXEncoder::<_, U8, nalgebra::U1>::new(&mut writer, nalgebra::Vector1::new(5))
+            .expect("Encoder creation error");
+        encoder.write_items(vec![1u8, 2]).expect("write error");
+        assert!(matches!(encoder.finish(), Err(IDXError::ItemCount(5, 2))));
+    }
+
+    #[test]
+    fn encode_1d_over_write_reports_items_actually_written() {
+        let mut writer = std::io::Cursor::new(Vec::new());
+        let mut encoder = IDXEncoder::<_, U8, nalgebra::U1>::new(&mut writer, nalgebra::Vector1::new(2))
+            .expect("Encoder creation error");
+        // The third item is rejected before anything is written for it, so
+        // the error should report 2 items written, not 3.
+        assert!(matches!(
+            encoder.write_items(vec![1u8, 2, 3]),
+            Err(IDXError::ItemCount(2, 2))
+        ));
+    }
+
+    #[test]
+    fn encode_1d_write_item_is_compatible_with_finish() {
+        let mut writer = std::io::Cursor::new(Vec::new());
+        let mut encoder = IDXEncoder::<_, U8, nalgebra::U1>::new(&mut writer, nalgebra::Vector1::new(3))
+            .expect("Encoder creation error");
+        encoder.write_item(&1).expect("write error");
+        encoder.write_item(&2).expect("write error");
+        encoder.write_item(&3).expect("write error");
+        encoder.finish().expect("item count mismatch");
+        assert_eq!(writer.into_inner(), &[0, 0, 8, 1, 0, 0, 0, 3, 1, 2, 3]);
+    }
+
+    #[test]
+    fn roundtrip_3d() {
+        let mut writer = std::io::Cursor::new(Vec::new());
+        let items = vec![vec![1u8, 2, 3, 4], vec![5, 6, 7, 8], vec![9, 10, 11, 12]];
+        let mut encoder = IDXEncoder::<_, U8, nalgebra::U3>::new(&mut writer, nalgebra::Vector3::new(3, 2, 2))
+            .expect("Encoder creation error");
+        encoder.write_items(items.clone()).expect("write error");
+        encoder.finish().expect("item count mismatch");
+
+        let reader = std::io::Cursor::new(writer.into_inner());
+        let decoder = IDXDecoder::<_, U8, nalgebra::U3>::new(reader)
+            .expect("Decoder creation error");
+        let decoded: Result<Vec<_>, _> = decoder.collect();
+        assert_eq!(decoded.unwrap(), items);
+    }
+
+    #[test]
+    fn encode_3d_wrong_item_length_is_error() {
+        let mut writer = std::io::Cursor::new(Vec::new());
+        let mut encoder = IDXEncoder::<_, U8, nalgebra::U3>::new(&mut writer, nalgebra::Vector3::new(2, 2, 2))
+            .expect("Encoder creation error");
+        // Each item should have 2*2 = 4 values; this one only has 3.
+        assert!(matches!(
+            encoder.write_items(vec![vec![1u8, 2, 3]]),
+            Err(IDXError::ItemLength(4, 3))
+        ));
+    }
+
+    #[test]
+    fn map_items_into_matrix() {
+        const DATA: &[u8] = &[
+            // magic, type u8, 3 dims: 2 matrices of 2x2
+            0, 0, 8, 3,
+            0, 0, 0, 2,
+            0, 0, 0, 2,
+            0, 0, 0, 2,
+            1, 2, 3, 4,
+            5, 6, 7, 8];
+        let reader = std::io::Cursor::new(DATA);
+        let decoder = IDXDecoder::<_, U8, nalgebra::U3>::new(reader)
+            .expect("Decoder creation error");
+        let matrices: Result<Vec<nalgebra::Matrix2<u8>>, _> = decoder.map_items().collect();
+        let matrices = matrices.unwrap();
+        assert_eq!(matrices[0], nalgebra::Matrix2::new(1, 2, 3, 4));
+        assert_eq!(matrices[1], nalgebra::Matrix2::new(5, 6, 7, 8));
+    }
+
+    #[test]
+    fn map_items_into_array() {
+        const DATA: &[u8] = &[
+            0, 0, 8, 2,
+            0, 0, 0, 2,
+            0, 0, 0, 3,
+            1, 2, 3,
+            4, 5, 6];
+        let reader = std::io::Cursor::new(DATA);
+        let decoder = IDXDecoder::<_, U8, nalgebra::U2>::new(reader)
+            .expect("Decoder creation error");
+        let rows: Result<Vec<[u8; 3]>, _> = decoder.map_items().collect();
+        assert_eq!(rows.unwrap(), vec![[1, 2, 3], [4, 5, 6]]);
+    }
+
+    #[test]
+    fn map_items_length_mismatch_is_error() {
+        const DATA: &[u8] = &[
+            // magic, type u8, 2 dims: 2 rows of 3
+            0, 0, 8, 2,
+            0, 0, 0, 2,
+            0, 0, 0, 3,
+            1, 2, 3,
+            4, 5, 6];
+        let reader = std::io::Cursor::new(DATA);
+        let mut decoder = IDXDecoder::<_, U8, nalgebra::U2>::new(reader)
+            .expect("Decoder creation error")
+            // each item has 3 values, but the target array wants 4
+            .map_items::<[u8; 4]>();
+        assert!(matches!(decoder.next(), Some(Err(IDXError::ItemLength(4, 3)))));
     }
 }